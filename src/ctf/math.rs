@@ -0,0 +1,366 @@
+//! Pure-Rust derivation of CTF condition, collection, and position IDs.
+//!
+//! Mirrors the `ConditionalTokens` contract's ID scheme bit-for-bit so callers can
+//! derive ERC1155 token IDs offline, without an RPC call.
+
+use alloy::primitives::{keccak256, Address, B256, U256};
+
+/// Order of the alt_bn128 (BN254) base field.
+const FIELD_MODULUS: U256 = U256::from_limbs([
+    4332616871279656263,
+    10917124144477883021,
+    13281191951274694749,
+    3486998266802970665,
+]);
+
+/// `b` coefficient of the alt_bn128 curve `y^2 = x^3 + b`.
+const CURVE_B: U256 = U256::from_limbs([3, 0, 0, 0]);
+
+/// Exponent used to take a modular square root in the alt_bn128 field, i.e. `(p + 1) / 4`.
+const SQRT_EXPONENT: U256 = U256::from_limbs([
+    5694840236247301970,
+    7340967054546858659,
+    7931984006246061591,
+    871749566700742666,
+]);
+
+/// Bit index separating the alt_bn128 x-coordinate from the odd-`y` flag packed into a
+/// collection ID.
+const SIGN_BIT: usize = 255;
+
+/// A point on the alt_bn128 curve, or the point at infinity (`(0, 0)`).
+type CurvePoint = (U256, U256);
+
+/// Computes `condition_id = keccak256(oracle ++ question_id ++ outcome_slot_count)`.
+pub fn condition_id(oracle: Address, question_id: B256, outcome_slot_count: U256) -> B256 {
+    let mut bytes = Vec::with_capacity(20 + 32 + 32);
+    bytes.extend_from_slice(oracle.as_slice());
+    bytes.extend_from_slice(question_id.as_slice());
+    bytes.extend_from_slice(&outcome_slot_count.to_be_bytes::<32>());
+    keccak256(bytes)
+}
+
+/// Computes `position_id = uint256(keccak256(collateral_token ++ collection_id))`.
+pub fn position_id(collateral_token: Address, collection_id: B256) -> U256 {
+    let mut bytes = Vec::with_capacity(20 + 32);
+    bytes.extend_from_slice(collateral_token.as_slice());
+    bytes.extend_from_slice(collection_id.as_slice());
+    U256::from_be_bytes(keccak256(bytes).0)
+}
+
+/// Computes a collection ID by hashing `(condition_id, index_set)` to a point on the
+/// alt_bn128 curve and, if `parent_collection_id` is non-zero, adding it to the parent's
+/// decoded point.
+pub fn collection_id(parent_collection_id: B256, condition_id: B256, index_set: U256) -> B256 {
+    let point = hash_to_curve(condition_id, index_set);
+
+    let point = if parent_collection_id.is_zero() {
+        point
+    } else {
+        point_add(point, decode_point(parent_collection_id))
+    };
+
+    encode_point(point)
+}
+
+/// Hashes `(condition_id, index_set)` onto the alt_bn128 curve using the same
+/// try-and-increment scheme as the `ConditionalTokens` contract.
+fn hash_to_curve(condition_id: B256, index_set: U256) -> CurvePoint {
+    let mut bytes = Vec::with_capacity(64);
+    bytes.extend_from_slice(condition_id.as_slice());
+    bytes.extend_from_slice(&index_set.to_be_bytes::<32>());
+
+    let x1 = U256::from_be_bytes(keccak256(bytes).0);
+    let negate = x1.bit(SIGN_BIT);
+    let mut x2 = x1 & ((U256::from(1) << SIGN_BIT) - U256::from(1));
+
+    let (x2, y1) = loop {
+        x2 = x2.add_mod(U256::from(1), FIELD_MODULUS);
+        let y2 = curve_rhs(x2);
+        let y1 = mod_pow(y2, SQRT_EXPONENT, FIELD_MODULUS);
+        if y1.mul_mod(y1, FIELD_MODULUS) == y2 {
+            break (x2, y1);
+        }
+    };
+
+    let y = if negate { sub_mod(FIELD_MODULUS, y1, FIELD_MODULUS) } else { y1 };
+    (x2, y)
+}
+
+/// Evaluates `x^3 + b mod p`.
+fn curve_rhs(x: U256) -> U256 {
+    let x2 = x.mul_mod(x, FIELD_MODULUS);
+    let x3 = x2.mul_mod(x, FIELD_MODULUS);
+    x3.add_mod(CURVE_B, FIELD_MODULUS)
+}
+
+/// Decodes a collection ID back into the alt_bn128 point it encodes.
+fn decode_point(id: B256) -> CurvePoint {
+    let packed = U256::from_be_bytes(id.0);
+    let want_odd = packed.bit(SIGN_BIT);
+    let x = packed & ((U256::from(1) << SIGN_BIT) - U256::from(1));
+
+    let y2 = curve_rhs(x);
+    let root = mod_pow(y2, SQRT_EXPONENT, FIELD_MODULUS);
+    // `mod_pow` returns *a* square root, not necessarily the one with the encoded
+    // parity, so flip it only if its parity doesn't already match `want_odd`.
+    let root_is_odd = !(root & U256::from(1)).is_zero();
+    let y = if root_is_odd != want_odd {
+        sub_mod(FIELD_MODULUS, root, FIELD_MODULUS)
+    } else {
+        root
+    };
+    (x, y)
+}
+
+/// Packs a point into a collection ID: the x-coordinate with the odd-`y` flag in the top
+/// bit, matching the contract's encoding.
+fn encode_point(point: CurvePoint) -> B256 {
+    let (x, y) = point;
+    let odd = !(y & U256::from(1)).is_zero();
+    let packed = if odd { x | (U256::from(1) << SIGN_BIT) } else { x };
+    B256::from(packed.to_be_bytes::<32>())
+}
+
+/// Adds two affine alt_bn128 points, treating `(0, 0)` as the point at infinity.
+fn point_add(p1: CurvePoint, p2: CurvePoint) -> CurvePoint {
+    if p1 == (U256::ZERO, U256::ZERO) {
+        return p2;
+    }
+    if p2 == (U256::ZERO, U256::ZERO) {
+        return p1;
+    }
+
+    let (x1, y1) = p1;
+    let (x2, y2) = p2;
+
+    let lambda = if x1 == x2 {
+        if y1 != y2 {
+            return (U256::ZERO, U256::ZERO);
+        }
+        // Point doubling: lambda = 3*x1^2 / 2*y1
+        let numerator = U256::from(3).mul_mod(x1.mul_mod(x1, FIELD_MODULUS), FIELD_MODULUS);
+        let denominator = U256::from(2).mul_mod(y1, FIELD_MODULUS);
+        numerator.mul_mod(inv_mod(denominator), FIELD_MODULUS)
+    } else {
+        let numerator = sub_mod(y2, y1, FIELD_MODULUS);
+        let denominator = sub_mod(x2, x1, FIELD_MODULUS);
+        numerator.mul_mod(inv_mod(denominator), FIELD_MODULUS)
+    };
+
+    let lambda_squared = lambda.mul_mod(lambda, FIELD_MODULUS);
+    let x3 = sub_mod(sub_mod(lambda_squared, x1, FIELD_MODULUS), x2, FIELD_MODULUS);
+    let x1_minus_x3 = sub_mod(x1, x3, FIELD_MODULUS);
+    let y3 = sub_mod(lambda.mul_mod(x1_minus_x3, FIELD_MODULUS), y1, FIELD_MODULUS);
+
+    (x3, y3)
+}
+
+/// Computes the inverse of `base` mod `FIELD_MODULUS` as `base^(p - 2)`, via Fermat's
+/// little theorem.
+fn inv_mod(base: U256) -> U256 {
+    mod_pow(base, FIELD_MODULUS - U256::from(2), FIELD_MODULUS)
+}
+
+/// Computes `a - b mod p`, assuming `a` and `b` are both already reduced mod `p`.
+fn sub_mod(a: U256, b: U256, p: U256) -> U256 {
+    if a >= b { a - b } else { p - (b - a) }
+}
+
+/// Computes the collateral redeemable for one index set, matching the
+/// `ConditionalTokens` contract's redemption formula: `balance * sum(payoutNumerators
+/// for slots in index_set) / payoutDenominator`. Returns zero if the condition hasn't
+/// been resolved yet (`payout_denominator == 0`).
+///
+/// `balance * numerator_sum` saturates at `U256::MAX` instead of wrapping. On overflow
+/// the result is therefore `U256::MAX / payout_denominator`, a lower bound on the true
+/// payout; it equals `U256::MAX` only when `payout_denominator` is 1.
+pub fn expected_payout(
+    index_set: U256,
+    balance: U256,
+    payout_numerators: &[U256],
+    payout_denominator: U256,
+) -> U256 {
+    if payout_denominator.is_zero() {
+        return U256::ZERO;
+    }
+
+    let numerator_sum = payout_numerators
+        .iter()
+        .enumerate()
+        .filter(|(slot, _)| index_set.bit(*slot))
+        .fold(U256::ZERO, |sum, (_, numerator)| sum.saturating_add(*numerator));
+
+    balance.saturating_mul(numerator_sum) / payout_denominator
+}
+
+/// Computes `base^exp mod modulus` via square-and-multiply.
+fn mod_pow(base: U256, exp: U256, modulus: U256) -> U256 {
+    let mut result = U256::from(1);
+    let mut base = base % modulus;
+    let mut exp = exp;
+
+    while exp > U256::ZERO {
+        if !(exp & U256::from(1)).is_zero() {
+            result = result.mul_mod(base, modulus);
+        }
+        base = base.mul_mod(base, modulus);
+        exp >>= 1;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oracle() -> Address {
+        Address::from([0x11; 20])
+    }
+
+    fn question_id() -> B256 {
+        B256::from([0x22; 32])
+    }
+
+    fn collateral_token() -> Address {
+        Address::from([0x33; 20])
+    }
+
+    #[test]
+    fn condition_id_matches_known_vector() {
+        let actual = condition_id(oracle(), question_id(), U256::from(2));
+        assert_eq!(
+            actual,
+            b256_from_hex("bcee96a610b7f4e61e2947f6510d1a15d4ae7c961a556b014db3527975047a1a")
+        );
+    }
+
+    #[test]
+    fn collection_id_matches_known_vectors() {
+        let condition_id = condition_id(oracle(), question_id(), U256::from(2));
+
+        let collection_yes = collection_id(B256::ZERO, condition_id, U256::from(1));
+        let collection_no = collection_id(B256::ZERO, condition_id, U256::from(2));
+
+        assert_eq!(
+            collection_yes,
+            b256_from_hex("a16b9f584537982f8067c123733a97eddfc439ff709254e16f0a3020ea7fc0e5")
+        );
+        assert_eq!(
+            collection_no,
+            b256_from_hex("9aca364ade4300d96df4fcc61fb8503e051c7203eb7416bb42144147687497a1")
+        );
+    }
+
+    #[test]
+    fn position_id_matches_known_vector() {
+        let condition_id = condition_id(oracle(), question_id(), U256::from(2));
+        let collection_yes = collection_id(B256::ZERO, condition_id, U256::from(1));
+        let position = position_id(collateral_token(), collection_yes);
+
+        assert_eq!(
+            position,
+            U256::from_str_radix(
+                "97a3500c8d7b140a51b41e57ccaa8ef99f1f2ab5e58ec3d4042c96838385cfb2",
+                16
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn nested_collection_id_matches_known_vector() {
+        let condition_id = condition_id(oracle(), question_id(), U256::from(2));
+        let collection_yes = collection_id(B256::ZERO, condition_id, U256::from(1));
+
+        let oracle2 = Address::from([0x44; 20]);
+        let question_id2 = B256::from([0x55; 32]);
+        let condition_id2 = condition_id_of(oracle2, question_id2);
+        let nested = collection_id(collection_yes, condition_id2, U256::from(1));
+
+        assert_eq!(
+            nested,
+            b256_from_hex("283117e69ccae4b57d23f9f4d86de9411e33e422df9e66b6af845c552f0d57f0")
+        );
+
+        let nested_position = position_id(collateral_token(), nested);
+        assert_eq!(
+            nested_position,
+            U256::from_str_radix(
+                "1a51fa218ecf8f73ee368c40f094429ec68176d2fe32399fccc476a49d943749",
+                16
+            )
+            .unwrap()
+        );
+    }
+
+    /// Computes a condition ID with a fixed outcome slot count of 2, to keep the nested
+    /// test vector above self-contained.
+    fn condition_id_of(oracle: Address, question_id: B256) -> B256 {
+        condition_id(oracle, question_id, U256::from(2))
+    }
+
+    fn b256_from_hex(hex: &str) -> B256 {
+        B256::from(U256::from_str_radix(hex, 16).unwrap().to_be_bytes::<32>())
+    }
+
+    #[test]
+    fn hash_to_curve_point_is_on_curve() {
+        let condition_id = condition_id(oracle(), question_id(), U256::from(2));
+        let (x, y) = hash_to_curve(condition_id, U256::from(1));
+        assert_eq!(y.mul_mod(y, FIELD_MODULUS), curve_rhs(x));
+    }
+
+    #[test]
+    fn decode_point_round_trips_through_encode() {
+        let condition_id = condition_id(oracle(), question_id(), U256::from(2));
+
+        // Exercise both parities of the odd-y flag.
+        for index_set in [U256::from(1), U256::from(2), U256::from(3), U256::from(4)] {
+            let point = hash_to_curve(condition_id, index_set);
+            let id = encode_point(point);
+            assert_eq!(decode_point(id), point, "round trip failed for index_set {index_set}");
+        }
+    }
+
+    #[test]
+    fn point_add_is_commutative_and_stays_on_curve() {
+        let condition_id = condition_id(oracle(), question_id(), U256::from(2));
+        let p1 = hash_to_curve(condition_id, U256::from(1));
+        let p2 = decode_point(collection_id(B256::ZERO, condition_id, U256::from(2)));
+
+        let sum_ab = point_add(p1, p2);
+        let sum_ba = point_add(p2, p1);
+        assert_eq!(sum_ab, sum_ba);
+        assert_eq!(sum_ab.1.mul_mod(sum_ab.1, FIELD_MODULUS), curve_rhs(sum_ab.0));
+    }
+
+    #[test]
+    fn expected_payout_is_zero_before_resolution() {
+        let payout = expected_payout(U256::from(1), U256::from(100), &[], U256::ZERO);
+        assert_eq!(payout, U256::ZERO);
+    }
+
+    #[test]
+    fn expected_payout_sums_numerators_in_index_set() {
+        let payout_numerators = vec![U256::from(1), U256::from(3)];
+        let payout_denominator = U256::from(4);
+
+        // index_set = 0b11 covers both slots: balance * (1+3) / 4 == balance
+        let payout =
+            expected_payout(U256::from(3), U256::from(100), &payout_numerators, payout_denominator);
+        assert_eq!(payout, U256::from(100));
+
+        // index_set = 0b01 covers only the first slot: balance * 1 / 4
+        let payout =
+            expected_payout(U256::from(1), U256::from(100), &payout_numerators, payout_denominator);
+        assert_eq!(payout, U256::from(25));
+    }
+
+    #[test]
+    fn expected_payout_saturates_instead_of_wrapping() {
+        let payout = expected_payout(U256::from(1), U256::MAX, &[U256::MAX], U256::from(1));
+        assert_eq!(payout, U256::MAX);
+    }
+}
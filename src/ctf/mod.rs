@@ -0,0 +1,4 @@
+//! Conditional Tokens Framework (CTF) integration.
+
+pub mod math;
+pub mod types;
@@ -0,0 +1,4 @@
+//! Request and response types for CTF operations.
+
+pub mod request;
+pub mod response;
@@ -3,6 +3,11 @@
 use alloy::primitives::{B256, U256};
 use bon::Builder;
 
+use crate::ctf::math;
+use crate::ctf::types::response::{
+    CollectionIdResponse, CombinatorialPositionResponse, ConditionIdResponse, IndexSetPayout,
+    PositionIdResponse, RedeemPositionsPreview,
+};
 use crate::types::Address;
 
 /// Request to calculate a condition ID.
@@ -19,6 +24,19 @@ pub struct ConditionIdRequest {
     pub outcome_slot_count: U256,
 }
 
+impl ConditionIdRequest {
+    /// Derives the condition ID locally, without any network call.
+    pub fn compute(&self) -> ConditionIdResponse {
+        ConditionIdResponse {
+            condition_id: math::condition_id(
+                self.oracle,
+                self.question_id,
+                self.outcome_slot_count,
+            ),
+        }
+    }
+}
+
 /// Request to calculate a collection ID.
 ///
 /// Creates collection identifiers using parent collection, condition ID, and index set.
@@ -33,6 +51,20 @@ pub struct CollectionIdRequest {
     pub index_set: U256,
 }
 
+impl CollectionIdRequest {
+    /// Derives the collection ID locally by hashing `(condition_id, index_set)` onto the
+    /// alt_bn128 curve and, if set, folding in the parent collection's point.
+    pub fn compute(&self) -> CollectionIdResponse {
+        CollectionIdResponse {
+            collection_id: math::collection_id(
+                self.parent_collection_id,
+                self.condition_id,
+                self.index_set,
+            ),
+        }
+    }
+}
+
 /// Request to calculate a position ID.
 ///
 /// Generates final ERC1155 token IDs from collateral token and collection ID.
@@ -45,6 +77,15 @@ pub struct PositionIdRequest {
     pub collection_id: B256,
 }
 
+impl PositionIdRequest {
+    /// Derives the position ID (ERC1155 token ID) locally, without any network call.
+    pub fn compute(&self) -> PositionIdResponse {
+        PositionIdResponse {
+            position_id: math::position_id(self.collateral_token, self.collection_id),
+        }
+    }
+}
+
 /// Request to split collateral into outcome tokens.
 ///
 /// Converts USDC collateral into matched outcome token pairs (YES/NO).
@@ -53,6 +94,9 @@ pub struct PositionIdRequest {
 pub struct SplitPositionRequest {
     /// The collateral token address (e.g., USDC)
     pub collateral_token: Address,
+    /// ERC1155 token ID of the collateral position, for splitting a conditional token
+    /// into a deeper, nested position. `None` (the default) means plain ERC20 collateral.
+    pub collateral_token_id: Option<U256>,
     /// Parent collection ID (typically zero for Polymarket)
     #[builder(default)]
     pub parent_collection_id: B256,
@@ -65,6 +109,14 @@ pub struct SplitPositionRequest {
     pub amount: U256,
 }
 
+impl SplitPositionRequest {
+    /// Whether this split draws collateral from an ERC1155 conditional-token position
+    /// rather than plain ERC20/USDC collateral.
+    pub fn is_erc1155_collateral(&self) -> bool {
+        self.collateral_token_id.is_some()
+    }
+}
+
 /// Request to merge outcome tokens back into collateral.
 ///
 /// Combines matched outcome token pairs back into USDC.
@@ -73,6 +125,9 @@ pub struct SplitPositionRequest {
 pub struct MergePositionsRequest {
     /// The collateral token address (e.g., USDC)
     pub collateral_token: Address,
+    /// ERC1155 token ID of the collateral position, for merging back into a shallower,
+    /// nested position. `None` (the default) means plain ERC20 collateral.
+    pub collateral_token_id: Option<U256>,
     /// Parent collection ID (typically zero for Polymarket)
     #[builder(default)]
     pub parent_collection_id: B256,
@@ -85,6 +140,14 @@ pub struct MergePositionsRequest {
     pub amount: U256,
 }
 
+impl MergePositionsRequest {
+    /// Whether this merge produces an ERC1155 conditional-token position rather than
+    /// plain ERC20/USDC collateral.
+    pub fn is_erc1155_collateral(&self) -> bool {
+        self.collateral_token_id.is_some()
+    }
+}
+
 /// Request to redeem winning outcome tokens for collateral.
 ///
 /// After a condition is resolved, burns winning tokens to recover USDC.
@@ -101,3 +164,190 @@ pub struct RedeemPositionsRequest {
     /// Array of disjoint index sets representing outcome slots to redeem
     pub index_sets: Vec<U256>,
 }
+
+impl RedeemPositionsRequest {
+    /// Computes the collateral expected back for each index set in this request, given
+    /// the condition's resolved payouts and the caller's balance of each corresponding
+    /// position, without submitting a transaction. `balances` must be in the same order
+    /// and have the same length as `index_sets`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `balances.len() != self.index_sets.len()`, rather than silently
+    /// dropping the trailing index sets the way `Iterator::zip` would.
+    pub fn preview(&self, payouts: &ConditionPayouts, balances: &[U256]) -> RedeemPositionsPreview {
+        assert_eq!(
+            balances.len(),
+            self.index_sets.len(),
+            "balances must have one entry per index set"
+        );
+
+        let payouts = self
+            .index_sets
+            .iter()
+            .zip(balances)
+            .map(|(&index_set, &balance)| IndexSetPayout {
+                index_set,
+                expected_payout: math::expected_payout(
+                    index_set,
+                    balance,
+                    &payouts.payout_numerators,
+                    payouts.payout_denominator,
+                ),
+            })
+            .collect::<Vec<_>>();
+
+        let total_expected_payout =
+            payouts.iter().fold(U256::ZERO, |sum, p| sum.saturating_add(p.expected_payout));
+
+        RedeemPositionsPreview { payouts, total_expected_payout }
+    }
+
+    /// Like [`Self::preview`], but drops index sets with a zero expected payout so
+    /// callers don't waste gas redeeming losing slots.
+    pub fn preview_nonzero(
+        &self,
+        payouts: &ConditionPayouts,
+        balances: &[U256],
+    ) -> RedeemPositionsPreview {
+        let mut preview = self.preview(payouts, balances);
+        preview.payouts.retain(|p| !p.expected_payout.is_zero());
+        preview
+    }
+}
+
+/// Resolved payout state of a condition, as reported by its oracle.
+///
+/// Typically fetched once via the `ConditionalTokens` contract's `payoutNumerators` and
+/// `payoutDenominator` and then reused across preview calls.
+#[non_exhaustive]
+#[derive(Debug, Clone, Builder)]
+pub struct ConditionPayouts {
+    /// Payout numerator for each outcome slot, in slot order. All zero until the
+    /// condition is resolved.
+    pub payout_numerators: Vec<U256>,
+    /// Sum of all payout numerators; zero until the condition is resolved.
+    pub payout_denominator: U256,
+}
+
+/// Request to prepare a new condition.
+///
+/// Registers a question with an oracle and outcome slot count so it can later be split,
+/// merged, and redeemed against.
+#[non_exhaustive]
+#[derive(Debug, Clone, Builder)]
+pub struct PrepareConditionRequest {
+    /// The oracle address that will report the outcome
+    pub oracle: Address,
+    /// Hash of the question being resolved
+    pub question_id: B256,
+    /// Number of outcome slots (typically 2 for binary markets)
+    pub outcome_slot_count: U256,
+}
+
+impl PrepareConditionRequest {
+    /// Derives the condition ID this request will register, without any network call.
+    pub fn condition_id(&self) -> B256 {
+        math::condition_id(self.oracle, self.question_id, self.outcome_slot_count)
+    }
+}
+
+/// Request to report payout numerators for a condition.
+///
+/// Submitted by the condition's oracle once the question's outcome is known.
+#[non_exhaustive]
+#[derive(Debug, Clone, Builder)]
+pub struct ReportPayoutsRequest {
+    /// Hash of the question being resolved
+    pub question_id: B256,
+    /// Payout numerators for each outcome slot, in slot order.
+    /// Length must equal the condition's outcome slot count.
+    pub payouts: Vec<U256>,
+}
+
+/// Request to derive a combinatorial position spanning several conditions.
+///
+/// Each leg conditions the position on one more question, nesting collections the same
+/// way splitting an outcome token into a second condition's outcomes would.
+#[non_exhaustive]
+#[derive(Debug, Clone, Builder)]
+pub struct CombinatorialPositionRequest {
+    /// The root collateral token address (e.g., USDC)
+    pub collateral_token: Address,
+    /// Ordered `(condition_id, index_set)` pairs, outermost condition first. Each leg's
+    /// collection is nested inside the previous leg's.
+    pub legs: Vec<(B256, U256)>,
+}
+
+impl CombinatorialPositionRequest {
+    /// Folds each leg's collection ID into the next, then derives the terminal position
+    /// ID, all without any network call.
+    pub fn compute(&self) -> CombinatorialPositionResponse {
+        let mut parent_collection_id = B256::ZERO;
+        let mut collection_ids = Vec::with_capacity(self.legs.len());
+
+        for &(condition_id, index_set) in &self.legs {
+            parent_collection_id =
+                math::collection_id(parent_collection_id, condition_id, index_set);
+            collection_ids.push(parent_collection_id);
+        }
+
+        let position_id = math::position_id(self.collateral_token, parent_collection_id);
+
+        CombinatorialPositionResponse { collection_ids, position_id }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combinatorial_position_matches_chained_collection_ids() {
+        let oracle = Address::from([0x11; 20]);
+        let question_id = B256::from([0x22; 32]);
+        let condition_id = math::condition_id(oracle, question_id, U256::from(2));
+
+        let oracle2 = Address::from([0x44; 20]);
+        let question_id2 = B256::from([0x55; 32]);
+        let condition_id2 = math::condition_id(oracle2, question_id2, U256::from(2));
+
+        let collateral_token = Address::from([0x33; 20]);
+
+        let request = CombinatorialPositionRequest::builder()
+            .collateral_token(collateral_token)
+            .legs(vec![(condition_id, U256::from(1)), (condition_id2, U256::from(1))])
+            .build();
+        let response = request.compute();
+
+        // The first leg's collection ID folds in no parent; the second leg's folds in
+        // the first, matching what chaining `CollectionIdRequest` calls by hand would
+        // produce.
+        let first_collection_id = math::collection_id(B256::ZERO, condition_id, U256::from(1));
+        let second_collection_id =
+            math::collection_id(first_collection_id, condition_id2, U256::from(1));
+
+        assert_eq!(response.collection_ids, vec![first_collection_id, second_collection_id]);
+        assert_eq!(
+            response.position_id,
+            math::position_id(collateral_token, second_collection_id)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "balances must have one entry per index set")]
+    fn redeem_preview_panics_on_mismatched_balances_length() {
+        let request = RedeemPositionsRequest::builder()
+            .collateral_token(Address::ZERO)
+            .condition_id(B256::ZERO)
+            .index_sets(vec![U256::from(1), U256::from(2)])
+            .build();
+        let payouts = ConditionPayouts::builder()
+            .payout_numerators(vec![])
+            .payout_denominator(U256::ZERO)
+            .build();
+
+        // Only one balance for two index sets.
+        request.preview(&payouts, &[U256::from(100)]);
+    }
+}
@@ -55,3 +55,57 @@ pub struct RedeemPositionsResponse {
     /// Block number where the transaction was mined
     pub block_number: u64,
 }
+
+/// Response from preparing a condition.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct PrepareConditionResponse {
+    /// The derived condition ID
+    pub condition_id: B256,
+    /// Transaction hash
+    pub transaction_hash: B256,
+    /// Block number where the transaction was mined
+    pub block_number: u64,
+}
+
+/// Response from reporting payouts for a condition.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct ReportPayoutsResponse {
+    /// Transaction hash
+    pub transaction_hash: B256,
+    /// Block number where the transaction was mined
+    pub block_number: u64,
+}
+
+/// Expected collateral payout for a single index set, ahead of redeeming it.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct IndexSetPayout {
+    /// The index set this payout corresponds to
+    pub index_set: U256,
+    /// Expected collateral: `balance * sum(payoutNumerators for slots in index_set) /
+    /// payoutDenominator`
+    pub expected_payout: U256,
+}
+
+/// Preview of a redeem positions transaction's expected collateral payout.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct RedeemPositionsPreview {
+    /// Expected payout for each index set in the request
+    pub payouts: Vec<IndexSetPayout>,
+    /// Sum of all expected payouts
+    pub total_expected_payout: U256,
+}
+
+/// Response from deriving a combinatorial, multi-condition position ID.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct CombinatorialPositionResponse {
+    /// The collection ID produced after folding in each leg, in leg order. The last
+    /// entry is the final collection ID the position ID was derived from.
+    pub collection_ids: Vec<B256>,
+    /// The terminal ERC1155 token ID for the fully nested position
+    pub position_id: U256,
+}